@@ -0,0 +1,235 @@
+//! Bit-level primitives used to compress the on-disk inverted index.
+//!
+//! Postings lists are stored as ascending doc-id gaps (Elias gamma coded) paired
+//! with their term frequencies (variable-byte coded), which is far more compact
+//! than writing raw `u32`s.
+
+/// Appends bits/bytes to an in-memory buffer.
+#[derive(Default)]
+pub struct BitsWriter {
+    bytes: Vec<u8>,
+    /// Number of bits already used in the last byte of `bytes` (0 means byte-aligned).
+    bit_pos: u8,
+}
+
+impl BitsWriter {
+    pub fn new() -> Self {
+        BitsWriter {
+            bytes: Vec::new(),
+            bit_pos: 0,
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Writes a single bit, starting a new byte when the current one is full.
+    pub fn write_bit(&mut self, bit: bool) {
+        if self.bit_pos == 0 {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 1 << (7 - self.bit_pos);
+        }
+
+        self.bit_pos = (self.bit_pos + 1) % 8;
+    }
+
+    /// Writes `n` as a variable-byte integer: 7 data bits per byte, high bit set
+    /// on the final (least-significant) byte.
+    pub fn write_vbyte(&mut self, mut n: u64) {
+        let mut groups = vec![(n & 0x7f) as u8];
+        n >>= 7;
+        while n > 0 {
+            groups.push((n & 0x7f) as u8);
+            n >>= 7;
+        }
+
+        for (i, group) in groups.iter().enumerate().rev() {
+            let byte = if i == 0 { group | 0x80 } else { *group };
+            self.write_byte_bits(byte);
+        }
+    }
+
+    /// Writes `n` (must be >= 1) using Elias gamma coding: `floor(log2(n))` zero
+    /// bits followed by the binary representation of `n`.
+    pub fn write_gamma(&mut self, n: u64) {
+        assert!(n >= 1, "gamma coding requires n >= 1");
+
+        let bits = 64 - n.leading_zeros();
+        for _ in 0..bits - 1 {
+            self.write_bit(false);
+        }
+        for i in (0..bits).rev() {
+            self.write_bit((n >> i) & 1 == 1);
+        }
+    }
+
+    /// Writes a sorted postings list (ascending doc ids with frequencies) as a
+    /// vbyte length, followed by gamma-coded id gaps and vbyte-coded frequencies.
+    pub fn write_postings(&mut self, postings: &[(usize, u32)]) {
+        self.write_vbyte(postings.len() as u64);
+
+        let mut prev = 0u64;
+        for &(doc_id, freq) in postings {
+            let doc_id = doc_id as u64;
+            let gap = doc_id - prev;
+            // Gamma coding requires n >= 1, so gaps are shifted by one.
+            self.write_gamma(gap + 1);
+            self.write_vbyte(freq as u64);
+            prev = doc_id;
+        }
+    }
+
+    /// Writes a length-prefixed raw byte slice (e.g. a UTF-8 string).
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_vbyte(bytes.len() as u64);
+        for &byte in bytes {
+            self.write_byte_bits(byte);
+        }
+    }
+
+    fn write_byte_bits(&mut self, byte: u8) {
+        for i in (0..8).rev() {
+            self.write_bit((byte >> i) & 1 == 1);
+        }
+    }
+}
+
+/// Reads bits/bytes previously produced by [`BitsWriter`].
+pub struct BitsReader<'a> {
+    bytes: &'a [u8],
+    bit_pos: usize,
+}
+
+impl<'a> BitsReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitsReader { bytes, bit_pos: 0 }
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        let byte_idx = self.bit_pos / 8;
+        let byte = *self.bytes.get(byte_idx)?;
+        let bit = (byte >> (7 - (self.bit_pos % 8))) & 1 == 1;
+        self.bit_pos += 1;
+        Some(bit)
+    }
+
+    pub fn read_vbyte(&mut self) -> Option<u64> {
+        let mut n = 0u64;
+        loop {
+            let mut byte = 0u8;
+            for _ in 0..8 {
+                byte = (byte << 1) | self.read_bit()? as u8;
+            }
+
+            n = (n << 7) | (byte & 0x7f) as u64;
+            if byte & 0x80 != 0 {
+                return Some(n);
+            }
+        }
+    }
+
+    pub fn read_gamma(&mut self) -> Option<u64> {
+        let mut zeros = 0u32;
+        while !self.read_bit()? {
+            zeros += 1;
+        }
+
+        let mut n = 1u64;
+        for _ in 0..zeros {
+            n = (n << 1) | self.read_bit()? as u64;
+        }
+
+        Some(n)
+    }
+
+    /// Reads a length-prefixed raw byte slice written by [`BitsWriter::write_bytes`].
+    pub fn read_bytes(&mut self) -> Option<Vec<u8>> {
+        let len = self.read_vbyte()?;
+        let mut bytes = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            let mut byte = 0u8;
+            for _ in 0..8 {
+                byte = (byte << 1) | self.read_bit()? as u8;
+            }
+            bytes.push(byte);
+        }
+        Some(bytes)
+    }
+
+    /// Reads a postings list written by [`BitsWriter::write_postings`].
+    pub fn read_postings(&mut self) -> Option<Vec<(usize, u32)>> {
+        let len = self.read_vbyte()?;
+        let mut postings = Vec::with_capacity(len as usize);
+
+        let mut prev = 0u64;
+        for _ in 0..len {
+            let gap = self.read_gamma()? - 1;
+            let doc_id = prev + gap;
+            let freq = self.read_vbyte()? as u32;
+            postings.push((doc_id as usize, freq));
+            prev = doc_id;
+        }
+
+        Some(postings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vbyte_roundtrip() {
+        let mut writer = BitsWriter::new();
+        for n in [0u64, 1, 127, 128, 300, 16384, u64::MAX] {
+            writer.write_vbyte(n);
+        }
+
+        let bytes = writer.into_bytes();
+        let mut reader = BitsReader::new(&bytes);
+        for n in [0u64, 1, 127, 128, 300, 16384, u64::MAX] {
+            assert_eq!(reader.read_vbyte(), Some(n));
+        }
+    }
+
+    #[test]
+    fn gamma_roundtrip() {
+        let mut writer = BitsWriter::new();
+        for n in [1u64, 2, 3, 4, 17, 1000, 65535] {
+            writer.write_gamma(n);
+        }
+
+        let bytes = writer.into_bytes();
+        let mut reader = BitsReader::new(&bytes);
+        for n in [1u64, 2, 3, 4, 17, 1000, 65535] {
+            assert_eq!(reader.read_gamma(), Some(n));
+        }
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let mut writer = BitsWriter::new();
+        writer.write_bytes(b"hello, oxidex");
+
+        let bytes = writer.into_bytes();
+        let mut reader = BitsReader::new(&bytes);
+        assert_eq!(reader.read_bytes(), Some(b"hello, oxidex".to_vec()));
+    }
+
+    #[test]
+    fn postings_roundtrip() {
+        let postings = vec![(1usize, 3u32), (4, 1), (10, 7), (11, 2)];
+
+        let mut writer = BitsWriter::new();
+        writer.write_postings(&postings);
+
+        let bytes = writer.into_bytes();
+        let mut reader = BitsReader::new(&bytes);
+        assert_eq!(reader.read_postings(), Some(postings));
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     path::PathBuf, time::UNIX_EPOCH,
 };
 
+#[derive(Clone)]
 pub struct DocumentEntry {
     pub id: usize,
     pub path: PathBuf,
@@ -0,0 +1,165 @@
+//! Metadata predicates layered on top of text search.
+//!
+//! [`SearchFilter`] restricts [`crate::oxidex::Oxidex::search_with_filter`] to
+//! documents whose [`crate::document::DocMetaData`] matches, and [`FacetField`]
+//! selects how [`crate::oxidex::Oxidex::facet_counts`] buckets matching documents.
+
+use crate::document::DocMetaData;
+
+/// Restricts search results to documents whose metadata matches every
+/// predicate set here. Unset predicates (the `SearchFilter::new()` default)
+/// match everything.
+#[derive(Default, Clone)]
+pub struct SearchFilter {
+    modified_after: Option<u64>,
+    modified_before: Option<u64>,
+    is_dir: Option<bool>,
+    permissions_mask: Option<u32>,
+}
+
+impl SearchFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps only documents modified at or after `timestamp` (unix seconds).
+    pub fn modified_after(mut self, timestamp: u64) -> Self {
+        self.modified_after = Some(timestamp);
+        self
+    }
+
+    /// Keeps only documents modified at or before `timestamp` (unix seconds).
+    pub fn modified_before(mut self, timestamp: u64) -> Self {
+        self.modified_before = Some(timestamp);
+        self
+    }
+
+    /// Keeps only directories (`true`) or only regular files (`false`).
+    pub fn is_dir(mut self, is_dir: bool) -> Self {
+        self.is_dir = Some(is_dir);
+        self
+    }
+
+    /// Keeps only documents whose permission bits contain every bit in `mask`.
+    pub fn permissions_mask(mut self, mask: u32) -> Self {
+        self.permissions_mask = Some(mask);
+        self
+    }
+
+    pub fn matches(&self, metadata: &DocMetaData) -> bool {
+        if let Some(after) = self.modified_after {
+            if metadata.modified_time < after {
+                return false;
+            }
+        }
+
+        if let Some(before) = self.modified_before {
+            if metadata.modified_time > before {
+                return false;
+            }
+        }
+
+        if let Some(is_dir) = self.is_dir {
+            if metadata.is_dir != is_dir {
+                return false;
+            }
+        }
+
+        if let Some(mask) = self.permissions_mask {
+            if metadata.permissions & mask != mask {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// The metadata dimension [`crate::oxidex::Oxidex::facet_counts`] buckets by.
+pub enum FacetField {
+    /// Buckets by age into `"this-week"`, `"this-month"`, or `"older"`.
+    ModifiedTime,
+    /// Buckets into `"directory"` or `"file"`.
+    IsDir,
+}
+
+const SECS_PER_WEEK: u64 = 7 * 24 * 60 * 60;
+const SECS_PER_MONTH: u64 = 30 * 24 * 60 * 60;
+
+/// Buckets `metadata` for `field`, given the current unix time in `now`.
+pub fn facet_bucket(field: &FacetField, metadata: &DocMetaData, now: u64) -> String {
+    match field {
+        FacetField::ModifiedTime => {
+            let age = now.saturating_sub(metadata.modified_time);
+            if age <= SECS_PER_WEEK {
+                "this-week".to_string()
+            } else if age <= SECS_PER_MONTH {
+                "this-month".to_string()
+            } else {
+                "older".to_string()
+            }
+        }
+        FacetField::IsDir => {
+            if metadata.is_dir {
+                "directory".to_string()
+            } else {
+                "file".to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(modified_time: u64, is_dir: bool, permissions: u32) -> DocMetaData {
+        DocMetaData {
+            create_time: 0,
+            modified_time,
+            permissions,
+            is_dir,
+        }
+    }
+
+    #[test]
+    fn filters_by_modified_range() {
+        let filter = SearchFilter::new().modified_after(100).modified_before(200);
+        assert!(filter.matches(&metadata(150, false, 0)));
+        assert!(!filter.matches(&metadata(50, false, 0)));
+        assert!(!filter.matches(&metadata(250, false, 0)));
+    }
+
+    #[test]
+    fn filters_by_is_dir_and_permissions() {
+        let filter = SearchFilter::new().is_dir(false).permissions_mask(0o444);
+        assert!(filter.matches(&metadata(0, false, 0o644)));
+        assert!(!filter.matches(&metadata(0, true, 0o644)));
+        assert!(!filter.matches(&metadata(0, false, 0o200)));
+    }
+
+    #[test]
+    fn buckets_by_age() {
+        let now = 10 * SECS_PER_MONTH;
+        assert_eq!(
+            facet_bucket(&FacetField::ModifiedTime, &metadata(now, false, 0), now),
+            "this-week"
+        );
+        assert_eq!(
+            facet_bucket(
+                &FacetField::ModifiedTime,
+                &metadata(now - SECS_PER_WEEK - 1, false, 0),
+                now
+            ),
+            "this-month"
+        );
+        assert_eq!(
+            facet_bucket(
+                &FacetField::ModifiedTime,
+                &metadata(now - SECS_PER_MONTH - 1, false, 0),
+                now
+            ),
+            "older"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Typo-tolerant term correction for [`crate::oxidex::Oxidex::search_fuzzy`].
+//!
+//! Vocabulary terms are indexed by the character k-grams they contain (e.g.
+//! `"cat"` padded to `"$cat$"` contributes the bigrams `$c`, `ca`, `at`, `t$`).
+//! An unmatched query term is corrected by shortlisting vocabulary terms that
+//! share k-grams, ranking the shortlist by Jaccard overlap, then breaking ties
+//! with Levenshtein edit distance.
+
+use std::collections::{HashMap, HashSet};
+
+type Token = String;
+
+const DEFAULT_GRAM_SIZE: usize = 2;
+const TOP_CANDIDATES: usize = 10;
+
+/// Maps character k-grams to the vocabulary terms containing them.
+pub struct KGramIndex {
+    index: HashMap<String, HashSet<Token>>,
+    k: usize,
+}
+
+impl KGramIndex {
+    pub fn new() -> Self {
+        Self::with_gram_size(DEFAULT_GRAM_SIZE)
+    }
+
+    pub fn with_gram_size(k: usize) -> Self {
+        KGramIndex {
+            index: HashMap::new(),
+            k,
+        }
+    }
+
+    /// Indexes `term` under every k-gram it contains.
+    pub fn insert(&mut self, term: &Token) {
+        for gram in kgrams(term, self.k) {
+            self.index.entry(gram).or_default().insert(term.clone());
+        }
+    }
+
+    /// Removes `term` from every k-gram bucket, pruning buckets left empty.
+    pub fn remove(&mut self, term: &Token) {
+        for gram in kgrams(term, self.k) {
+            if let Some(terms) = self.index.get_mut(&gram) {
+                terms.remove(term);
+                if terms.is_empty() {
+                    self.index.remove(&gram);
+                }
+            }
+        }
+    }
+
+    fn candidates(&self, term: &str) -> HashSet<Token> {
+        let mut candidates = HashSet::new();
+        for gram in kgrams(term, self.k) {
+            if let Some(terms) = self.index.get(&gram) {
+                candidates.extend(terms.iter().cloned());
+            }
+        }
+        candidates
+    }
+
+    /// Finds the best spelling correction for `term`, or `None` if none of the
+    /// top-ranked candidates sharing a k-gram with it is within `max_edits`.
+    /// Vocabulary terms that share no k-gram with `term`, or rank below the
+    /// first `TOP_CANDIDATES`, are not considered.
+    pub fn correct(&self, term: &str, max_edits: usize) -> Option<Token> {
+        let term_grams = kgrams(term, self.k);
+
+        let mut scored: Vec<(Token, f32, usize)> = self
+            .candidates(term)
+            .into_iter()
+            .map(|candidate| {
+                let candidate_grams = kgrams(&candidate, self.k);
+                let similarity = jaccard(&term_grams, &candidate_grams);
+                let distance = levenshtein(term, &candidate);
+                (candidate, similarity, distance)
+            })
+            .collect();
+
+        // Rank by Jaccard overlap, breaking ties (not the overall order) with
+        // Levenshtein distance, so a higher-Jaccard candidate always outranks
+        // a lower-Jaccard one regardless of edit distance.
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.2.cmp(&b.2))
+        });
+        scored.truncate(TOP_CANDIDATES);
+
+        scored
+            .into_iter()
+            .find(|&(_, _, distance)| distance <= max_edits)
+            .map(|(term, _, _)| term)
+    }
+}
+
+impl Default for KGramIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn kgrams(term: &str, k: usize) -> HashSet<String> {
+    let padded = format!("${term}$");
+    let chars: Vec<char> = padded.chars().collect();
+
+    if chars.len() < k {
+        return std::iter::once(padded).collect();
+    }
+
+    chars.windows(k).map(|window| window.iter().collect()).collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let intersection = a.intersection(b).count() as f32;
+    let union = a.union(b).count() as f32;
+    intersection / union
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corrects_single_typo() {
+        let mut index = KGramIndex::new();
+        for term in ["cat", "car", "dog"] {
+            index.insert(&term.to_string());
+        }
+
+        assert_eq!(index.correct("cet", 1), Some("cat".to_string()));
+    }
+
+    #[test]
+    fn gives_up_past_max_edits() {
+        let mut index = KGramIndex::new();
+        index.insert(&"cat".to_string());
+
+        assert_eq!(index.correct("zzzzz", 1), None);
+    }
+
+    #[test]
+    fn remove_prunes_empty_buckets() {
+        let mut index = KGramIndex::new();
+        index.insert(&"cat".to_string());
+        index.remove(&"cat".to_string());
+
+        assert!(index.index.is_empty());
+    }
+
+    #[test]
+    fn levenshtein_matches_known_distances() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("cat", "cat"), 0);
+    }
+}
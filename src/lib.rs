@@ -0,0 +1,14 @@
+pub mod bits;
+pub mod document;
+pub mod filter;
+pub mod fuzzy;
+pub mod oxidex;
+pub mod preprocessor;
+pub mod segment;
+pub mod stem;
+pub mod stopwords;
+
+pub use document::{DocMetaData, DocumentEntry};
+pub use filter::{FacetField, SearchFilter};
+pub use oxidex::{Oxidex, OxidexError, SearchResult};
+pub use preprocessor::{Preprocessor, PreprocessorBuilder};
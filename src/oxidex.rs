@@ -1,17 +1,56 @@
-use std::{collections::HashMap, fs, path::PathBuf};
-
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::bits::{BitsReader, BitsWriter};
 use crate::document::DocumentEntry;
+use crate::filter::{facet_bucket, FacetField, SearchFilter};
+use crate::fuzzy::KGramIndex;
+use crate::preprocessor::Preprocessor;
+use crate::segment::{merge_segments, MemSegment, Segment};
 
 type Token = String;
 
+/// Segments are merged once more than this many accumulate, keeping segment count from
+/// growing without bound as the corpus is ingested.
+const DEFAULT_MAX_SEGMENTS: usize = 4;
+
+/// A [`MemSegment`] is flushed once it holds this many tokens.
+const DEFAULT_FLUSH_THRESHOLD: usize = 10_000;
+
+#[derive(Debug)]
 pub enum OxidexError {
     AddDocumentError(String),
+    SaveError(String),
+    LoadError(String),
 }
 
+/// A segmented, BM25-ranked text index over a file corpus.
+///
+/// New documents accumulate in an in-memory segment; once it crosses
+/// `flush_threshold` tokens it's frozen into an immutable [`Segment`], and
+/// once too many segments have piled up they're combined with a k-way merge.
+/// `search` and friends query across the active segment and every frozen one.
 pub struct Oxidex {
-    documents: HashMap<usize, DocumentEntry>,
-    inverted_index: HashMap<Token, HashMap<usize, u32>>,
+    active: MemSegment,
+    segments: Vec<Segment>,
+    /// Doc ids removed since the last merge; purged from frozen segments the
+    /// next time they're merged together.
+    tombstones: HashSet<usize>,
     next_idx: usize,
+    next_segment_id: usize,
+    flush_threshold: usize,
+    max_segments: usize,
+    /// BM25 term frequency saturation parameter.
+    pub k1: f32,
+    /// BM25 document length normalization parameter.
+    pub b: f32,
+    preprocessor: Preprocessor,
+    kgram_index: KGramIndex,
 }
 
 pub struct SearchResult {
@@ -19,76 +58,401 @@ pub struct SearchResult {
     pub score: f32,
 }
 
+/// Orders [`SearchResult`]s by score so they can live in a [`std::collections::BinaryHeap`].
+/// NaN scores are treated as equal, matching the sort fallback used elsewhere.
+struct ScoredResult(SearchResult);
+
+impl PartialEq for ScoredResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.score == other.0.score
+    }
+}
+
+impl Eq for ScoredResult {}
+
+impl PartialOrd for ScoredResult {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredResult {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0
+            .score
+            .partial_cmp(&other.0.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
 impl Oxidex {
     pub fn new() -> Self {
+        Self::with_preprocessor(Preprocessor::new())
+    }
+
+    /// Builds an `Oxidex` that tokenizes with a caller-supplied [`Preprocessor`],
+    /// e.g. one with stemming disabled or a custom stopword set.
+    pub fn with_preprocessor(preprocessor: Preprocessor) -> Self {
         Oxidex {
-            documents: HashMap::new(),
-            inverted_index: HashMap::new(),
+            active: MemSegment::new(),
+            segments: Vec::new(),
+            tombstones: HashSet::new(),
             next_idx: 0,
+            next_segment_id: 0,
+            flush_threshold: DEFAULT_FLUSH_THRESHOLD,
+            max_segments: DEFAULT_MAX_SEGMENTS,
+            k1: 1.2,
+            b: 0.75,
+            preprocessor,
+            kgram_index: KGramIndex::new(),
         }
     }
 
+    /// Overrides the token count at which the active segment is flushed.
+    pub fn with_flush_threshold(mut self, flush_threshold: usize) -> Self {
+        self.flush_threshold = flush_threshold;
+        self
+    }
+
     pub fn add_document(&mut self, path: PathBuf) -> Result<(), OxidexError> {
         let raw_bytes =
             fs::read(&path).map_err(|e| OxidexError::AddDocumentError(e.to_string()))?;
         // Todo:
         // Implement parsers for different file types. At the moment we just convert a byte vector into a String
         let parsed_content = String::from_utf8_lossy(&raw_bytes).into_owned();
-        let tokens: Vec<Token> = parsed_content
-            .split_ascii_whitespace()
-            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_lowercase())
-            .collect();
+        let tokens: Vec<Token> = self.preprocessor.tokenize(&parsed_content);
 
-        for token in tokens.clone() {
-            self.inverted_index
-                .entry(token)
-                .or_default()
-                .entry(self.next_idx)
-                .and_modify(|count| *count += 1)
-                .or_insert(1);
+        for token in &tokens {
+            self.kgram_index.insert(token);
         }
 
         let doc_entry = DocumentEntry::new(self.next_idx, path, tokens.len())
             .map_err(|e| OxidexError::AddDocumentError(e.to_string()))?;
 
-        self.documents.insert(self.next_idx, doc_entry);
+        self.active.insert(doc_entry, &tokens);
         self.next_idx += 1;
+
+        if self.active.token_count() >= self.flush_threshold {
+            self.flush();
+        }
+
         Ok(())
     }
 
-    /// Removes the document from Oxidex, by id.
+    /// Freezes the active segment and, once more than `max_segments` frozen
+    /// segments exist, merges them all into one.
+    fn flush(&mut self) {
+        if self.active.documents().is_empty() {
+            return;
+        }
+
+        let flushed = std::mem::take(&mut self.active).freeze(self.next_segment_id);
+        self.next_segment_id += 1;
+        self.segments.push(flushed);
+
+        if self.segments.len() > self.max_segments {
+            self.merge_all_segments();
+        }
+    }
+
+    /// Combines every frozen segment into one via [`merge_segments`], purging
+    /// tombstoned documents and any vocabulary terms they leave orphaned.
+    fn merge_all_segments(&mut self) {
+        let segments = std::mem::take(&mut self.segments);
+        let id = self.next_segment_id;
+        self.next_segment_id += 1;
+
+        let (merged, dropped_terms) = merge_segments(segments, &self.tombstones, id);
+        for term in &dropped_terms {
+            self.kgram_index.remove(term);
+        }
+
+        self.tombstones.clear();
+        self.segments = vec![merged];
+    }
+
+    /// Removes the document from Oxidex, by id. The removal is recorded as a
+    /// tombstone and only physically purged from frozen segments on the next
+    /// merge; the (still mutable) active segment is purged immediately.
     pub fn remove_id(&mut self, id: usize) -> bool {
-        let existed = self.documents.remove(&id).is_some();
+        let existed = self.get_doc(id).is_some();
+        if !existed {
+            return false;
+        }
+
+        self.tombstones.insert(id);
+
+        if self.active.documents().contains_key(&id) {
+            for term in self.active.remove(id) {
+                // The term may still be live in a frozen segment; only a merge
+                // (which sees every segment at once) can tell for sure.
+                if !self.term_in_any_segment(&term) {
+                    self.kgram_index.remove(&term);
+                }
+            }
+        }
+
+        true
+    }
+
+    fn term_in_any_segment(&self, term: &Token) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| segment.postings(term).is_some())
+    }
+
+    /// Serializes the index to `path` using gap + variable-byte + Elias gamma
+    /// compressed postings, so a process can reload without re-ingesting the
+    /// corpus. This is a read-only snapshot across the active segment and
+    /// every frozen segment — it does not flush or merge anything, so the
+    /// segment layout a later `save` or query sees is unaffected by calling
+    /// this.
+    pub fn save(&self, path: PathBuf) -> Result<(), OxidexError> {
+        let mut documents: HashMap<usize, &DocumentEntry> = HashMap::new();
+        for doc in self.active.documents().values() {
+            documents.insert(doc.id, doc);
+        }
+        for segment in &self.segments {
+            for doc in segment.documents().values() {
+                if !self.tombstones.contains(&doc.id) {
+                    documents.insert(doc.id, doc);
+                }
+            }
+        }
+
+        let mut terms: HashSet<&Token> = HashSet::new();
+        terms.extend(self.active.terms());
+        for segment in &self.segments {
+            terms.extend(segment.terms());
+        }
+
+        let mut writer = BitsWriter::new();
+        writer.write_vbyte(self.next_idx as u64);
+        writer.write_bytes(&self.k1.to_be_bytes());
+        writer.write_bytes(&self.b.to_be_bytes());
+        writer.write_bit(self.preprocessor.stem_enabled());
 
-        for (_, doc_freq_map) in self.inverted_index.iter_mut() {
-            doc_freq_map.remove(&id);
+        let stopwords: Vec<&Token> = self.preprocessor.stopwords().iter().collect();
+        writer.write_vbyte(stopwords.len() as u64);
+        for word in stopwords {
+            writer.write_bytes(word.as_bytes());
         }
-        self.inverted_index
-            .retain(|_, doc_freq_map| !doc_freq_map.is_empty());
-        existed
+
+        writer.write_vbyte(documents.len() as u64);
+        for doc in documents.values() {
+            writer.write_vbyte(doc.id as u64);
+            writer.write_bytes(doc.path.to_string_lossy().as_bytes());
+            writer.write_vbyte(doc.token_count as u64);
+            writer.write_vbyte(doc.metadata.create_time);
+            writer.write_vbyte(doc.metadata.modified_time);
+            writer.write_vbyte(doc.metadata.permissions as u64);
+            writer.write_bit(doc.metadata.is_dir);
+        }
+
+        writer.write_vbyte(terms.len() as u64);
+        for term in terms {
+            writer.write_bytes(term.as_bytes());
+            let mut postings = self.postings(term);
+            postings.sort_by_key(|&(doc_id, _)| doc_id);
+            writer.write_postings(&postings);
+        }
+
+        fs::write(path, writer.into_bytes()).map_err(|e| OxidexError::SaveError(e.to_string()))
+    }
+
+    /// Loads an index previously written by [`Oxidex::save`] as a single
+    /// frozen segment.
+    pub fn load(path: PathBuf) -> Result<Self, OxidexError> {
+        let bytes = fs::read(path).map_err(|e| OxidexError::LoadError(e.to_string()))?;
+        let mut reader = BitsReader::new(&bytes);
+
+        let eof = || OxidexError::LoadError("unexpected end of index file".to_string());
+
+        let next_idx = reader.read_vbyte().ok_or_else(eof)? as usize;
+
+        let k1_bytes = reader.read_bytes().ok_or_else(eof)?;
+        let k1 = f32::from_be_bytes(k1_bytes.try_into().map_err(|_| eof())?);
+        let b_bytes = reader.read_bytes().ok_or_else(eof)?;
+        let b = f32::from_be_bytes(b_bytes.try_into().map_err(|_| eof())?);
+        let stem = reader.read_bit().ok_or_else(eof)?;
+
+        let stopword_count = reader.read_vbyte().ok_or_else(eof)?;
+        let mut stopwords = Vec::with_capacity(stopword_count as usize);
+        for _ in 0..stopword_count {
+            let word_bytes = reader.read_bytes().ok_or_else(eof)?;
+            stopwords.push(String::from_utf8_lossy(&word_bytes).into_owned());
+        }
+        let preprocessor = Preprocessor::builder()
+            .stopwords(stopwords)
+            .stem(stem)
+            .build();
+
+        let doc_count = reader.read_vbyte().ok_or_else(eof)?;
+        let mut segment = MemSegment::new();
+        let mut loaded_docs = Vec::with_capacity(doc_count as usize);
+        for _ in 0..doc_count {
+            let id = reader.read_vbyte().ok_or_else(eof)? as usize;
+            let path_bytes = reader.read_bytes().ok_or_else(eof)?;
+            let path = PathBuf::from(String::from_utf8_lossy(&path_bytes).into_owned());
+            let token_count = reader.read_vbyte().ok_or_else(eof)? as usize;
+            let create_time = reader.read_vbyte().ok_or_else(eof)?;
+            let modified_time = reader.read_vbyte().ok_or_else(eof)?;
+            let permissions = reader.read_vbyte().ok_or_else(eof)? as u32;
+            let is_dir = reader.read_bit().ok_or_else(eof)?;
+
+            loaded_docs.push((
+                id,
+                path,
+                token_count,
+                create_time,
+                modified_time,
+                permissions,
+                is_dir,
+            ));
+        }
+
+        let term_count = reader.read_vbyte().ok_or_else(eof)?;
+        let mut kgram_index = KGramIndex::new();
+        let mut postings_by_term = Vec::with_capacity(term_count as usize);
+        for _ in 0..term_count {
+            let term_bytes = reader.read_bytes().ok_or_else(eof)?;
+            let term = String::from_utf8_lossy(&term_bytes).into_owned();
+            let postings = reader.read_postings().ok_or_else(eof)?;
+            kgram_index.insert(&term);
+            postings_by_term.push((term, postings));
+        }
+
+        // Reassemble each document's token list (every term repeated `freq`
+        // times), the shape `MemSegment::insert` expects, then immediately
+        // freeze it into a single segment.
+        let mut tokens_by_doc: HashMap<usize, Vec<Token>> = HashMap::new();
+        for (term, postings) in postings_by_term {
+            for (doc_id, freq) in postings {
+                let entry = tokens_by_doc.entry(doc_id).or_default();
+                for _ in 0..freq {
+                    entry.push(term.clone());
+                }
+            }
+        }
+
+        for (id, path, token_count, create_time, modified_time, permissions, is_dir) in
+            loaded_docs
+        {
+            let doc = DocumentEntry {
+                id,
+                path,
+                metadata: crate::document::DocMetaData {
+                    create_time,
+                    modified_time,
+                    permissions,
+                    is_dir,
+                },
+                token_count,
+            };
+            let tokens = tokens_by_doc.remove(&id).unwrap_or_default();
+            segment.insert(doc, &tokens);
+        }
+
+        Ok(Oxidex {
+            active: MemSegment::new(),
+            segments: vec![segment.freeze(0)],
+            tombstones: HashSet::new(),
+            next_idx,
+            next_segment_id: 1,
+            flush_threshold: DEFAULT_FLUSH_THRESHOLD,
+            max_segments: DEFAULT_MAX_SEGMENTS,
+            k1,
+            b,
+            preprocessor,
+            kgram_index,
+        })
     }
 
     pub fn get_doc(&self, doc_id: usize) -> Option<&DocumentEntry> {
-        self.documents.get(&doc_id)
+        if let Some(doc) = self.active.documents().get(&doc_id) {
+            return Some(doc);
+        }
+
+        if self.tombstones.contains(&doc_id) {
+            return None;
+        }
+
+        self.segments
+            .iter()
+            .find_map(|segment| segment.documents().get(&doc_id))
     }
 
-    pub fn search(&self, query: Token) -> Vec<SearchResult> {
-        // Need to build a query.
-        // Using the formula sum(TF(t, d)* IDF(t)) for all t in Q, we can get the score
-        // of a file based on the search query.
-        let mut search_results: Vec<SearchResult> = Vec::new();
+    /// Searches for `query` and ranks matching documents with Okapi BM25.
+    ///
+    /// The query is tokenized with the same pipeline as `add_document`, scores
+    /// from every query term are accumulated per document, and matches are
+    /// drawn from the active segment plus every live frozen segment.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let mut search_results: Vec<SearchResult> = self
+            .score_query(query)
+            .into_iter()
+            .map(|(doc_id, score)| SearchResult { doc_id, score })
+            .collect();
+
+        search_results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        search_results
+    }
+
+    /// Like [`Oxidex::search`], but only materializes the top `k` results using a
+    /// bounded min-heap instead of sorting the full result set. This keeps the hot
+    /// path at O(n log k) and bounds memory for huge result sets.
+    pub fn search_top_k(&self, query: &str, k: usize) -> Vec<SearchResult> {
+        if k == 0 {
+            return Vec::new();
+        }
 
-        if let Some(data) = self.inverted_index.get(&query) {
-            for doc_id in data.keys() {
-                search_results.push(SearchResult {
-                    doc_id: *doc_id,
-                    score: self.get_normalised_tf_idf(&query, *doc_id),
-                });
+        let mut heap: BinaryHeap<Reverse<ScoredResult>> = BinaryHeap::with_capacity(k + 1);
+        for (doc_id, score) in self.score_query(query) {
+            heap.push(Reverse(ScoredResult(SearchResult { doc_id, score })));
+            if heap.len() > k {
+                heap.pop();
             }
         }
 
+        let mut search_results: Vec<SearchResult> = Vec::with_capacity(heap.len());
+        while let Some(Reverse(ScoredResult(result))) = heap.pop() {
+            search_results.push(result);
+        }
+        search_results.reverse();
+
+        search_results
+    }
+
+    /// Like [`Oxidex::search`], but an unmatched query term is first replaced
+    /// with its best spelling correction (by k-gram Jaccard overlap, tie-broken
+    /// on Levenshtein distance) as long as the correction is within `max_edits`.
+    pub fn search_fuzzy(&self, query: &str, max_edits: usize) -> Vec<SearchResult> {
+        let corrected_tokens: Vec<Token> = self
+            .preprocessor
+            .tokenize(query)
+            .into_iter()
+            .map(|token| {
+                if self.doc_frequency(&token) > 0 {
+                    token
+                } else {
+                    self.kgram_index
+                        .correct(&token, max_edits)
+                        .unwrap_or(token)
+                }
+            })
+            .collect();
+
+        let mut search_results: Vec<SearchResult> = self
+            .score_tokens(&corrected_tokens)
+            .into_iter()
+            .map(|(doc_id, score)| SearchResult { doc_id, score })
+            .collect();
+
         search_results.sort_by(|a, b| {
             b.score
                 .partial_cmp(&a.score)
@@ -98,40 +462,139 @@ impl Oxidex {
         search_results
     }
 
-    fn term_frequency(&self, token: &Token, id: usize) -> f32 {
-        self.inverted_index
-            .get(token)
-            .and_then(|entry| entry.get(&id))
-            .copied()
-            .unwrap_or(0) as f32
+    /// Like [`Oxidex::search`], but restricted to documents whose metadata
+    /// matches `filter`.
+    pub fn search_with_filter(&self, query: &str, filter: &SearchFilter) -> Vec<SearchResult> {
+        let mut search_results: Vec<SearchResult> = self
+            .score_query(query)
+            .into_iter()
+            .filter(|(doc_id, _)| {
+                self.get_doc(*doc_id)
+                    .is_some_and(|doc| filter.matches(&doc.metadata))
+            })
+            .map(|(doc_id, score)| SearchResult { doc_id, score })
+            .collect();
+
+        search_results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        search_results
     }
 
-    fn inverse_document_frequency(&self, token: &Token) -> f32 {
-        let df_t = self
-            .inverted_index
-            .get(token)
-            .map(|inner| inner.len())
-            .unwrap_or(0) as f32;
+    /// Counts documents matching `query`, bucketed by `field`, so a UI can
+    /// offer refinement without a second search pass.
+    pub fn facet_counts(&self, query: &str, field: FacetField) -> HashMap<String, usize> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for doc_id in self.score_query(query).into_keys() {
+            let Some(doc) = self.get_doc(doc_id) else {
+                continue;
+            };
+
+            let bucket = facet_bucket(&field, &doc.metadata, now);
+            *counts.entry(bucket).or_insert(0) += 1;
+        }
 
-        let n = self.documents.len() as f32;
+        counts
+    }
 
-        (n / (df_t + 1.0)).log10()
+    /// Tokenizes `query` and accumulates BM25 scores across all matching query
+    /// terms, keyed by doc id.
+    fn score_query(&self, query: &str) -> HashMap<usize, f32> {
+        self.score_tokens(&self.preprocessor.tokenize(query))
     }
 
-    fn get_tf_idf(&self, token: &Token, id: usize) -> f32 {
-        self.term_frequency(token, id) * self.inverse_document_frequency(token)
+    /// Accumulates BM25 scores across all matching `tokens`, keyed by doc id.
+    fn score_tokens(&self, tokens: &[Token]) -> HashMap<usize, f32> {
+        let avgdl = self.average_doc_length();
+
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for token in tokens {
+            let idf = self.inverse_document_frequency(token);
+            for (doc_id, freq) in self.postings(token) {
+                let score = self.bm25_term_score(doc_id, freq, idf, avgdl);
+                *scores.entry(doc_id).or_insert(0.0) += score;
+            }
+        }
+
+        scores
     }
 
-    fn get_normalised_tf_idf(&self, token: &Token, id: usize) -> f32 {
-        let tf_idf = self.get_tf_idf(token, id);
+    /// Collects `(doc_id, frequency)` pairs for `term` across the active
+    /// segment and every live frozen segment, skipping tombstoned doc ids.
+    fn postings(&self, term: &Token) -> Vec<(usize, u32)> {
+        let mut postings = self.active.postings(term).unwrap_or_default();
+
+        for segment in &self.segments {
+            if let Some(segment_postings) = segment.postings(term) {
+                postings.extend(
+                    segment_postings
+                        .iter()
+                        .filter(|(doc_id, _)| !self.tombstones.contains(doc_id)),
+                );
+            }
+        }
 
-        let len = self
-            .documents
-            .get(&id)
-            .map(|doc| doc.token_count)
-            .unwrap_or(1) as f32;
+        postings
+    }
 
-        tf_idf / len.sqrt()
+    fn doc_frequency(&self, term: &Token) -> usize {
+        self.postings(term).len()
+    }
+
+    fn total_documents(&self) -> usize {
+        let mut ids: HashSet<usize> = self.active.documents().keys().copied().collect();
+        for segment in &self.segments {
+            ids.extend(
+                segment
+                    .documents()
+                    .keys()
+                    .filter(|id| !self.tombstones.contains(id)),
+            );
+        }
+        ids.len()
+    }
+
+    fn average_doc_length(&self) -> f32 {
+        let total_documents = self.total_documents();
+        if total_documents == 0 {
+            return 0.0;
+        }
+
+        let active_tokens: usize = self.active.documents().values().map(|d| d.token_count).sum();
+        let segment_tokens: usize = self
+            .segments
+            .iter()
+            .flat_map(|segment| segment.documents().iter())
+            .filter(|(id, _)| !self.tombstones.contains(id))
+            .map(|(_, doc)| doc.token_count)
+            .sum();
+
+        (active_tokens + segment_tokens) as f32 / total_documents as f32
+    }
+
+    fn inverse_document_frequency(&self, token: &Token) -> f32 {
+        let df_t = self.doc_frequency(token) as f32;
+        let n = self.total_documents() as f32;
+
+        ((n - df_t + 0.5) / (df_t + 0.5) + 1.0).ln()
+    }
+
+    fn bm25_term_score(&self, doc_id: usize, freq: u32, idf: f32, avgdl: f32) -> f32 {
+        let freq = freq as f32;
+        let doc_len = self.get_doc(doc_id).map(|doc| doc.token_count).unwrap_or(0) as f32;
+
+        let denom_avgdl = if avgdl > 0.0 { doc_len / avgdl } else { 0.0 };
+        let denom = freq + self.k1 * (1.0 - self.b + self.b * denom_avgdl);
+
+        idf * (freq * (self.k1 + 1.0)) / denom
     }
 }
 
@@ -144,10 +607,8 @@ impl Default for Oxidex {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::{self, File};
-    use std::io::Write;
+    use std::fs;
     use std::path::Path;
-    use tempfile::tempdir;
 
     struct TestDir {
         path: PathBuf,
@@ -170,7 +631,7 @@ mod tests {
             let _ = fs::remove_dir_all(&self.path);
         }
     }
-    
+
     #[test]
     fn test_with_raii_temp() -> std::io::Result<()> {
         let dir = TestDir::new("test_with_raii_temp")?;
@@ -182,4 +643,111 @@ mod tests {
 
         Ok(())
     }
+
+    fn write_doc(dir: &Path, name: &str, contents: &str) -> std::io::Result<PathBuf> {
+        let path = dir.join(name);
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn bm25_ranks_more_relevant_documents_higher() -> std::io::Result<()> {
+        let dir = TestDir::new("bm25_ranks_more_relevant_documents_higher")?;
+        let mut index = Oxidex::new();
+
+        let relevant = write_doc(dir.path(), "relevant.txt", "rust rust rust systems")?;
+        let passing_mention = write_doc(dir.path(), "passing.txt", "rust is mentioned once here")?;
+        index.add_document(relevant).unwrap();
+        index.add_document(passing_mention).unwrap();
+
+        let results = index.search("rust");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_id, 0, "the doc repeating the term should rank first");
+        assert!(results[0].score > results[1].score);
+
+        Ok(())
+    }
+
+    #[test]
+    fn search_top_k_returns_best_k_in_descending_order() -> std::io::Result<()> {
+        let dir = TestDir::new("search_top_k_returns_best_k_in_descending_order")?;
+        let mut index = Oxidex::new();
+
+        index.add_document(write_doc(dir.path(), "a.txt", "rust rust rust")?).unwrap();
+        index.add_document(write_doc(dir.path(), "b.txt", "rust rust")?).unwrap();
+        index.add_document(write_doc(dir.path(), "c.txt", "rust")?).unwrap();
+
+        let results = index.search_top_k("rust", 2);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_id, 0);
+        assert_eq!(results[1].doc_id, 1);
+        assert!(results[0].score >= results[1].score);
+
+        Ok(())
+    }
+
+    #[test]
+    fn search_fuzzy_corrects_a_typo() -> std::io::Result<()> {
+        let dir = TestDir::new("search_fuzzy_corrects_a_typo")?;
+        let mut index = Oxidex::new();
+        index.add_document(write_doc(dir.path(), "doc.txt", "hello world")?).unwrap();
+
+        let results = index.search_fuzzy("helo", 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_id, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn search_with_filter_excludes_non_matching_metadata() -> std::io::Result<()> {
+        let dir = TestDir::new("search_with_filter_excludes_non_matching_metadata")?;
+        let mut index = Oxidex::new();
+        index.add_document(write_doc(dir.path(), "doc.txt", "rust programming")?).unwrap();
+
+        let matching_everything = SearchFilter::new();
+        assert_eq!(index.search_with_filter("rust", &matching_everything).len(), 1);
+
+        let matching_directories_only = SearchFilter::new().is_dir(true);
+        assert!(index
+            .search_with_filter("rust", &matching_directories_only)
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn facet_counts_buckets_matching_documents() -> std::io::Result<()> {
+        let dir = TestDir::new("facet_counts_buckets_matching_documents")?;
+        let mut index = Oxidex::new();
+        index.add_document(write_doc(dir.path(), "doc.txt", "rust programming")?).unwrap();
+
+        let counts = index.facet_counts("rust", FacetField::IsDir);
+        assert_eq!(counts.get("file"), Some(&1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_and_load_round_trip_preserves_documents_and_postings() -> std::io::Result<()> {
+        let dir = TestDir::new("save_and_load_round_trip_preserves_documents_and_postings")?;
+        let mut index = Oxidex::new();
+        index.add_document(write_doc(dir.path(), "a.txt", "rust rust systems")?).unwrap();
+        index.add_document(write_doc(dir.path(), "b.txt", "rust programming")?).unwrap();
+
+        let index_path = dir.path().join("index.bin");
+        index.save(index_path.clone()).unwrap();
+        let reloaded = Oxidex::load(index_path).unwrap();
+
+        assert!(reloaded.get_doc(0).is_some());
+        assert!(reloaded.get_doc(1).is_some());
+        assert_eq!(reloaded.get_doc(0).unwrap().token_count, 3);
+
+        let before = index.search("rust");
+        let after = reloaded.search("rust");
+        assert_eq!(before.len(), after.len());
+        assert_eq!(before[0].doc_id, after[0].doc_id);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,138 @@
+//! Shared tokenization pipeline for both document ingestion and query parsing.
+//!
+//! [`Preprocessor`] replaces the ASCII-only, hard-coded tokenizer that used to
+//! live inside `add_document`: it Unicode-word-segments text, drops stopwords,
+//! and (by default) reduces terms to their Porter stem so that "running" and
+//! "runs" collapse to the same `inverted_index` entry.
+
+use std::collections::HashSet;
+
+use crate::stem::porter_stem;
+use crate::stopwords::DEFAULT_STOPWORDS;
+
+type Token = String;
+
+/// Tokenizes free text into the normalized terms stored in `Oxidex`'s inverted
+/// index. Construct one with [`Preprocessor::new`] for the defaults, or
+/// [`Preprocessor::builder`] to customize stopwords or disable stemming.
+pub struct Preprocessor {
+    stopwords: HashSet<Token>,
+    stem: bool,
+}
+
+impl Preprocessor {
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> PreprocessorBuilder {
+        PreprocessorBuilder::new()
+    }
+
+    /// Segments `text` into Unicode words, lowercases them, drops stopwords,
+    /// and (unless disabled) stems the remainder.
+    pub fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut word = String::new();
+
+        for ch in text.chars().chain(std::iter::once(' ')) {
+            if ch.is_alphanumeric() {
+                word.extend(ch.to_lowercase());
+                continue;
+            }
+
+            if !word.is_empty() {
+                if !self.stopwords.contains(&word) {
+                    tokens.push(if self.stem {
+                        porter_stem(&word)
+                    } else {
+                        std::mem::take(&mut word)
+                    });
+                }
+                word.clear();
+            }
+        }
+
+        tokens
+    }
+
+    /// Whether terms are reduced to their Porter stem.
+    pub fn stem_enabled(&self) -> bool {
+        self.stem
+    }
+
+    /// The stopwords dropped during tokenization.
+    pub fn stopwords(&self) -> &HashSet<Token> {
+        &self.stopwords
+    }
+}
+
+impl Default for Preprocessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for [`Preprocessor`], defaulting to [`DEFAULT_STOPWORDS`] with
+/// stemming enabled.
+pub struct PreprocessorBuilder {
+    stopwords: HashSet<Token>,
+    stem: bool,
+}
+
+impl PreprocessorBuilder {
+    pub fn new() -> Self {
+        PreprocessorBuilder {
+            stopwords: DEFAULT_STOPWORDS.iter().map(|s| s.to_string()).collect(),
+            stem: true,
+        }
+    }
+
+    /// Replaces the default stopword set.
+    pub fn stopwords(mut self, stopwords: impl IntoIterator<Item = Token>) -> Self {
+        self.stopwords = stopwords.into_iter().collect();
+        self
+    }
+
+    /// Enables or disables Porter stemming (enabled by default).
+    pub fn stem(mut self, enabled: bool) -> Self {
+        self.stem = enabled;
+        self
+    }
+
+    pub fn build(self) -> Preprocessor {
+        Preprocessor {
+            stopwords: self.stopwords,
+            stem: self.stem,
+        }
+    }
+}
+
+impl Default for PreprocessorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_inflections_to_the_same_stem() {
+        let preprocessor = Preprocessor::new();
+        assert_eq!(preprocessor.tokenize("running"), preprocessor.tokenize("runs"));
+    }
+
+    #[test]
+    fn drops_stopwords() {
+        let preprocessor = Preprocessor::new();
+        assert_eq!(preprocessor.tokenize("the cat and the hat"), vec!["cat", "hat"]);
+    }
+
+    #[test]
+    fn can_disable_stemming() {
+        let preprocessor = Preprocessor::builder().stem(false).build();
+        assert_eq!(preprocessor.tokenize("running"), vec!["running"]);
+    }
+}
@@ -0,0 +1,264 @@
+//! Segmented index storage.
+//!
+//! New documents accumulate in a mutable [`MemSegment`] until it crosses a
+//! token threshold, at which point [`Oxidex`](crate::oxidex::Oxidex) freezes it
+//! into an immutable [`Segment`]. [`merge_segments`] combines several frozen
+//! segments with a k-way merge over their sorted postings, which is how the
+//! segment count is kept from growing without bound and how tombstoned
+//! documents are finally purged.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+
+use crate::document::DocumentEntry;
+
+type Token = String;
+
+/// The mutable segment new documents are appended to.
+pub struct MemSegment {
+    documents: HashMap<usize, DocumentEntry>,
+    inverted_index: HashMap<Token, HashMap<usize, u32>>,
+    token_count: usize,
+}
+
+impl MemSegment {
+    pub fn new() -> Self {
+        MemSegment {
+            documents: HashMap::new(),
+            inverted_index: HashMap::new(),
+            token_count: 0,
+        }
+    }
+
+    pub fn insert(&mut self, doc: DocumentEntry, tokens: &[Token]) {
+        self.token_count += tokens.len();
+
+        for token in tokens {
+            self.inverted_index
+                .entry(token.clone())
+                .or_default()
+                .entry(doc.id)
+                .and_modify(|count| *count += 1)
+                .or_insert(1);
+        }
+
+        self.documents.insert(doc.id, doc);
+    }
+
+    /// Removes `id`, returning the terms whose postings became empty (so the
+    /// caller can prune any auxiliary indexes keyed on vocabulary terms).
+    pub fn remove(&mut self, id: usize) -> Vec<Token> {
+        self.documents.remove(&id);
+
+        let mut orphaned = Vec::new();
+        self.inverted_index.retain(|term, postings| {
+            postings.remove(&id);
+            let keep = !postings.is_empty();
+            if !keep {
+                orphaned.push(term.clone());
+            }
+            keep
+        });
+
+        orphaned
+    }
+
+    pub fn documents(&self) -> &HashMap<usize, DocumentEntry> {
+        &self.documents
+    }
+
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+
+    pub fn postings(&self, term: &Token) -> Option<Vec<(usize, u32)>> {
+        self.inverted_index
+            .get(term)
+            .map(|postings| postings.iter().map(|(&id, &freq)| (id, freq)).collect())
+    }
+
+    pub fn terms(&self) -> impl Iterator<Item = &Token> {
+        self.inverted_index.keys()
+    }
+
+    /// Freezes this segment into an immutable, queryable [`Segment`].
+    pub fn freeze(self, id: usize) -> Segment {
+        let mut postings: BTreeMap<Token, Vec<(usize, u32)>> = BTreeMap::new();
+        for (term, doc_freqs) in self.inverted_index {
+            let mut list: Vec<(usize, u32)> = doc_freqs.into_iter().collect();
+            list.sort_by_key(|&(doc_id, _)| doc_id);
+            postings.insert(term, list);
+        }
+
+        Segment {
+            id,
+            documents: self.documents,
+            postings,
+        }
+    }
+}
+
+impl Default for MemSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An immutable segment, either flushed directly from a [`MemSegment`] or
+/// produced by [`merge_segments`]. Postings are kept in a `BTreeMap` so
+/// [`merge_segments`] can walk segments' terms in lockstep.
+pub struct Segment {
+    id: usize,
+    documents: HashMap<usize, DocumentEntry>,
+    postings: BTreeMap<Token, Vec<(usize, u32)>>,
+}
+
+impl Segment {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn documents(&self) -> &HashMap<usize, DocumentEntry> {
+        &self.documents
+    }
+
+    pub fn postings(&self, term: &Token) -> Option<&Vec<(usize, u32)>> {
+        self.postings.get(term)
+    }
+
+    pub fn terms(&self) -> impl Iterator<Item = &Token> {
+        self.postings.keys()
+    }
+}
+
+/// Combines `segments` with a k-way merge over their sorted postings: a
+/// min-heap tracks each segment's current term, equal terms are concatenated
+/// and summed per doc id, and tombstoned doc ids are dropped. Returns the
+/// merged segment plus every term that was dropped entirely (so the caller
+/// can prune auxiliary indexes, e.g. the fuzzy k-gram index).
+pub fn merge_segments(
+    segments: Vec<Segment>,
+    tombstones: &HashSet<usize>,
+    new_id: usize,
+) -> (Segment, Vec<Token>) {
+    let mut documents = HashMap::new();
+    for segment in &segments {
+        for (&doc_id, doc) in &segment.documents {
+            if !tombstones.contains(&doc_id) {
+                documents.insert(doc_id, doc.clone());
+            }
+        }
+    }
+
+    let mut iters: Vec<_> = segments
+        .iter()
+        .map(|segment| segment.postings.iter().peekable())
+        .collect();
+
+    let mut heap: BinaryHeap<Reverse<(Token, usize)>> = BinaryHeap::new();
+    for (idx, it) in iters.iter_mut().enumerate() {
+        if let Some((term, _)) = it.peek() {
+            heap.push(Reverse(((*term).clone(), idx)));
+        }
+    }
+
+    let mut merged: BTreeMap<Token, Vec<(usize, u32)>> = BTreeMap::new();
+    let mut dropped_terms = Vec::new();
+
+    while let Some(Reverse((term, _))) = heap.peek().cloned() {
+        let mut combined: HashMap<usize, u32> = HashMap::new();
+
+        while let Some((head_term, idx)) = heap.peek().map(|Reverse((t, idx))| (t.clone(), *idx)) {
+            if head_term != term {
+                break;
+            }
+            heap.pop();
+
+            let (_, postings) = iters[idx].next().expect("heap entry implies a head");
+            for &(doc_id, freq) in postings {
+                if !tombstones.contains(&doc_id) {
+                    *combined.entry(doc_id).or_insert(0) += freq;
+                }
+            }
+
+            if let Some((next_term, _)) = iters[idx].peek() {
+                heap.push(Reverse(((*next_term).clone(), idx)));
+            }
+        }
+
+        if combined.is_empty() {
+            dropped_terms.push(term);
+            continue;
+        }
+
+        let mut postings: Vec<(usize, u32)> = combined.into_iter().collect();
+        postings.sort_by_key(|&(doc_id, _)| doc_id);
+        merged.insert(term, postings);
+    }
+
+    (
+        Segment {
+            id: new_id,
+            documents,
+            postings: merged,
+        },
+        dropped_terms,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::DocMetaData;
+    use std::path::PathBuf;
+
+    fn doc(id: usize, token_count: usize) -> DocumentEntry {
+        DocumentEntry {
+            id,
+            path: PathBuf::from(format!("doc-{id}.txt")),
+            metadata: DocMetaData {
+                create_time: 0,
+                modified_time: 0,
+                permissions: 0,
+                is_dir: false,
+            },
+            token_count,
+        }
+    }
+
+    #[test]
+    fn freezes_and_merges_disjoint_segments() {
+        let mut a = MemSegment::new();
+        a.insert(doc(1, 2), &["cat".to_string(), "dog".to_string()]);
+
+        let mut b = MemSegment::new();
+        b.insert(doc(2, 1), &["cat".to_string()]);
+
+        let segment_a = a.freeze(0);
+        let segment_b = b.freeze(1);
+
+        let (merged, dropped) = merge_segments(vec![segment_a, segment_b], &HashSet::new(), 2);
+
+        assert!(dropped.is_empty());
+        assert_eq!(merged.documents().len(), 2);
+        assert_eq!(
+            merged.postings(&"cat".to_string()).unwrap().len(),
+            2,
+            "cat appears in both segments"
+        );
+        assert_eq!(merged.postings(&"dog".to_string()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn merge_drops_tombstoned_documents_and_orphaned_terms() {
+        let mut a = MemSegment::new();
+        a.insert(doc(1, 1), &["unique".to_string()]);
+        let segment_a = a.freeze(0);
+
+        let tombstones: HashSet<usize> = [1].into_iter().collect();
+        let (merged, dropped) = merge_segments(vec![segment_a], &tombstones, 1);
+
+        assert!(merged.documents().is_empty());
+        assert_eq!(dropped, vec!["unique".to_string()]);
+    }
+}
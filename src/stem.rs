@@ -0,0 +1,331 @@
+//! A Rust port of the Porter stemming algorithm (Porter, 1980), used by
+//! [`crate::preprocessor::Preprocessor`] to collapse inflected forms like
+//! "running"/"runs" down to a shared stem before they enter the inverted index.
+
+fn is_vowel(chars: &[char], i: usize) -> bool {
+    match chars[i] {
+        'a' | 'e' | 'i' | 'o' | 'u' => true,
+        'y' => i == 0 || !is_vowel(chars, i - 1),
+        _ => false,
+    }
+}
+
+/// The "measure" `m` of a word: the number of vowel-consonant sequences in
+/// `chars[..=end]`, per Porter's `[C](VC)^m[V]` definition.
+fn measure(chars: &[char], end: usize) -> usize {
+    let mut m = 0;
+    let mut prev_vowel = false;
+    for i in 0..=end {
+        let vowel = is_vowel(chars, i);
+        if prev_vowel && !vowel {
+            m += 1;
+        }
+        prev_vowel = vowel;
+    }
+    m
+}
+
+fn contains_vowel(chars: &[char], end: usize) -> bool {
+    (0..=end).any(|i| is_vowel(chars, i))
+}
+
+fn ends_with_double_consonant(chars: &[char], end: usize) -> bool {
+    end > 0 && chars[end] == chars[end - 1] && !is_vowel(chars, end)
+}
+
+/// True if `chars[..=end]` ends in consonant-vowel-consonant where the final
+/// consonant is not W, X or Y (Porter's `*o` condition).
+fn ends_cvc(chars: &[char], end: usize) -> bool {
+    if end < 2 {
+        return false;
+    }
+    !is_vowel(chars, end)
+        && is_vowel(chars, end - 1)
+        && !is_vowel(chars, end - 2)
+        && !matches!(chars[end], 'w' | 'x' | 'y')
+}
+
+fn ends_with(chars: &[char], end: usize, suffix: &str) -> bool {
+    let suffix: Vec<char> = suffix.chars().collect();
+    if suffix.len() > end + 1 {
+        return false;
+    }
+    chars[end + 1 - suffix.len()..=end] == suffix[..]
+}
+
+/// Replaces the `suffix` ending `chars[..=end]` with `replacement`, returning
+/// the resulting word.
+fn replace_suffix(chars: &[char], end: usize, suffix: &str, replacement: &str) -> Vec<char> {
+    let stem_len = end + 1 - suffix.chars().count();
+    let mut result: Vec<char> = chars[..stem_len].to_vec();
+    result.extend(replacement.chars());
+    result
+}
+
+/// A `(suffix, replacement, condition)` triple for [`apply_rules`]: `condition`
+/// is checked against the measure of the stem preceding `suffix`.
+type StemRule = (&'static str, &'static str, fn(&[char], usize) -> bool);
+
+/// Applies a list of `(suffix, replacement, condition)` rules, firing the first
+/// whose suffix matches and whose condition holds against the stem measure.
+fn apply_rules(chars: Vec<char>, rules: &[StemRule]) -> Vec<char> {
+    if chars.is_empty() {
+        return chars;
+    }
+    let end = chars.len() - 1;
+
+    for &(suffix, replacement, condition) in rules {
+        if ends_with(&chars, end, suffix) {
+            let stem_end = end as isize - suffix.chars().count() as isize;
+            if stem_end >= 0 && condition(&chars, stem_end as usize) {
+                return replace_suffix(&chars, end, suffix, replacement);
+            }
+        }
+    }
+
+    chars
+}
+
+fn step1a(chars: Vec<char>) -> Vec<char> {
+    if chars.is_empty() {
+        return chars;
+    }
+    let end = chars.len() - 1;
+
+    if ends_with(&chars, end, "sses") {
+        replace_suffix(&chars, end, "sses", "ss")
+    } else if ends_with(&chars, end, "ies") {
+        replace_suffix(&chars, end, "ies", "i")
+    } else if ends_with(&chars, end, "ss") {
+        chars
+    } else if ends_with(&chars, end, "s") {
+        replace_suffix(&chars, end, "s", "")
+    } else {
+        chars
+    }
+}
+
+fn step1b(chars: Vec<char>) -> Vec<char> {
+    if chars.is_empty() {
+        return chars;
+    }
+    let end = chars.len() - 1;
+
+    let (mut word, double_suffix_fired) = if ends_with(&chars, end, "eed") {
+        let stem_end = end as isize - 3;
+        if stem_end >= 0 && measure(&chars, stem_end as usize) > 0 {
+            (replace_suffix(&chars, end, "eed", "ee"), false)
+        } else {
+            (chars, false)
+        }
+    } else if ends_with(&chars, end, "ed") && contains_vowel(&chars, end.saturating_sub(2)) {
+        (replace_suffix(&chars, end, "ed", ""), true)
+    } else if ends_with(&chars, end, "ing") && contains_vowel(&chars, end.saturating_sub(3)) {
+        (replace_suffix(&chars, end, "ing", ""), true)
+    } else {
+        (chars, false)
+    };
+
+    if !double_suffix_fired || word.is_empty() {
+        return word;
+    }
+
+    let end = word.len() - 1;
+    if ends_with(&word, end, "at") {
+        word = replace_suffix(&word, end, "at", "ate");
+    } else if ends_with(&word, end, "bl") {
+        word = replace_suffix(&word, end, "bl", "ble");
+    } else if ends_with(&word, end, "iz") {
+        word = replace_suffix(&word, end, "iz", "ize");
+    } else if ends_with_double_consonant(&word, end) && !matches!(word[end], 'l' | 's' | 'z') {
+        word.pop();
+    } else if measure(&word, word.len() - 1) == 1 && ends_cvc(&word, word.len() - 1) {
+        word.push('e');
+    }
+
+    word
+}
+
+fn step1c(chars: Vec<char>) -> Vec<char> {
+    if chars.is_empty() {
+        return chars;
+    }
+    let end = chars.len() - 1;
+    if ends_with(&chars, end, "y") && contains_vowel(&chars, end.saturating_sub(1)) {
+        replace_suffix(&chars, end, "y", "i")
+    } else {
+        chars
+    }
+}
+
+fn step2(chars: Vec<char>) -> Vec<char> {
+    apply_rules(
+        chars,
+        &[
+            ("ational", "ate", m_gt_fn(0)),
+            ("tional", "tion", m_gt_fn(0)),
+            ("enci", "ence", m_gt_fn(0)),
+            ("anci", "ance", m_gt_fn(0)),
+            ("izer", "ize", m_gt_fn(0)),
+            ("abli", "able", m_gt_fn(0)),
+            ("alli", "al", m_gt_fn(0)),
+            ("entli", "ent", m_gt_fn(0)),
+            ("eli", "e", m_gt_fn(0)),
+            ("ousli", "ous", m_gt_fn(0)),
+            ("ization", "ize", m_gt_fn(0)),
+            ("ation", "ate", m_gt_fn(0)),
+            ("ator", "ate", m_gt_fn(0)),
+            ("alism", "al", m_gt_fn(0)),
+            ("iveness", "ive", m_gt_fn(0)),
+            ("fulness", "ful", m_gt_fn(0)),
+            ("ousness", "ous", m_gt_fn(0)),
+            ("aliti", "al", m_gt_fn(0)),
+            ("iviti", "ive", m_gt_fn(0)),
+            ("biliti", "ble", m_gt_fn(0)),
+        ],
+    )
+}
+
+fn step3(chars: Vec<char>) -> Vec<char> {
+    apply_rules(
+        chars,
+        &[
+            ("icate", "ic", m_gt_fn(0)),
+            ("ative", "", m_gt_fn(0)),
+            ("alize", "al", m_gt_fn(0)),
+            ("iciti", "ic", m_gt_fn(0)),
+            ("ical", "ic", m_gt_fn(0)),
+            ("ful", "", m_gt_fn(0)),
+            ("ness", "", m_gt_fn(0)),
+        ],
+    )
+}
+
+fn step4(chars: Vec<char>) -> Vec<char> {
+    if chars.is_empty() {
+        return chars;
+    }
+    let end = chars.len() - 1;
+
+    // "ion" only strips after S or T, so it needs a bespoke check.
+    if ends_with(&chars, end, "ion") && end >= 3 && matches!(chars[end - 3], 's' | 't') {
+        let stem_end = end as isize - 3;
+        if stem_end >= 0 && measure(&chars, stem_end as usize) > 1 {
+            return replace_suffix(&chars, end, "ion", "");
+        }
+    }
+
+    apply_rules(
+        chars,
+        &[
+            ("al", "", m_gt_fn(1)),
+            ("ance", "", m_gt_fn(1)),
+            ("ence", "", m_gt_fn(1)),
+            ("er", "", m_gt_fn(1)),
+            ("ic", "", m_gt_fn(1)),
+            ("able", "", m_gt_fn(1)),
+            ("ible", "", m_gt_fn(1)),
+            ("ant", "", m_gt_fn(1)),
+            ("ement", "", m_gt_fn(1)),
+            ("ment", "", m_gt_fn(1)),
+            ("ent", "", m_gt_fn(1)),
+            ("ou", "", m_gt_fn(1)),
+            ("ism", "", m_gt_fn(1)),
+            ("ate", "", m_gt_fn(1)),
+            ("iti", "", m_gt_fn(1)),
+            ("ous", "", m_gt_fn(1)),
+            ("ive", "", m_gt_fn(1)),
+            ("ize", "", m_gt_fn(1)),
+        ],
+    )
+}
+
+fn step5a(chars: Vec<char>) -> Vec<char> {
+    if chars.is_empty() {
+        return chars;
+    }
+    let end = chars.len() - 1;
+    if !ends_with(&chars, end, "e") {
+        return chars;
+    }
+
+    let stem_end = end as isize - 1;
+    let m = if stem_end >= 0 {
+        measure(&chars, stem_end as usize)
+    } else {
+        0
+    };
+
+    if m > 1 || (m == 1 && stem_end >= 0 && !ends_cvc(&chars, stem_end as usize)) {
+        replace_suffix(&chars, end, "e", "")
+    } else {
+        chars
+    }
+}
+
+fn step5b(chars: Vec<char>) -> Vec<char> {
+    if chars.len() < 2 {
+        return chars;
+    }
+    let end = chars.len() - 1;
+    if chars[end] == 'l' && ends_with_double_consonant(&chars, end) && measure(&chars, end) > 1 {
+        let mut chars = chars;
+        chars.pop();
+        chars
+    } else {
+        chars
+    }
+}
+
+fn m_gt_fn(min: usize) -> fn(&[char], usize) -> bool {
+    match min {
+        0 => |chars: &[char], end: usize| measure(chars, end) > 0,
+        _ => |chars: &[char], end: usize| measure(chars, end) > 1,
+    }
+}
+
+/// Reduces `word` to its Porter stem. Words shorter than 3 characters are
+/// returned unchanged, matching the reference implementation's short-word guard.
+pub fn porter_stem(word: &str) -> String {
+    if word.chars().count() <= 2 {
+        return word.to_string();
+    }
+
+    let chars: Vec<char> = word.chars().collect();
+    let chars = step1a(chars);
+    let chars = step1b(chars);
+    let chars = step1c(chars);
+    let chars = step2(chars);
+    let chars = step3(chars);
+    let chars = step4(chars);
+    let chars = step5a(chars);
+    let chars = step5b(chars);
+
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::porter_stem;
+
+    #[test]
+    fn stems_common_inflections() {
+        assert_eq!(porter_stem("running"), "run");
+        assert_eq!(porter_stem("runs"), "run");
+        assert_eq!(porter_stem("caresses"), "caress");
+        assert_eq!(porter_stem("ponies"), "poni");
+        assert_eq!(porter_stem("agreed"), "agre");
+        assert_eq!(porter_stem("plastered"), "plaster");
+        assert_eq!(porter_stem("motoring"), "motor");
+        assert_eq!(porter_stem("happy"), "happi");
+        assert_eq!(porter_stem("relational"), "relat");
+        assert_eq!(porter_stem("conditional"), "condit");
+        assert_eq!(porter_stem("adoption"), "adopt");
+    }
+
+    #[test]
+    fn leaves_short_words_alone() {
+        assert_eq!(porter_stem("a"), "a");
+        assert_eq!(porter_stem("at"), "at");
+    }
+}